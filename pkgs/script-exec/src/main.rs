@@ -1,10 +1,13 @@
 use clap::Parser;
 use env_logger;
-use std::path::Path;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::process::{Command, exit};
+use std::sync::mpsc::channel;
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 mod output_manager;
 mod printer;
@@ -40,6 +43,40 @@ struct Args {
     #[arg(long = "label", value_parser = parse_label_pair, action = clap::ArgAction::Append)]
     key_values: Option<Vec<(String, String)>>,
 
+    /// Declare that the 'child' check must only run after 'parent' has passed
+    /// (format 'child:parent'), may be passed multiple times
+    #[arg(long = "depends", value_parser = parse_depends_pair, action = clap::ArgAction::Append)]
+    depends: Option<Vec<(String, String)>>,
+
+    /// Re-run affected checks whenever their script (or a --watch-path
+    /// directory) changes, instead of exiting after the first run
+    #[arg(long, default_value_t = false)]
+    watch: bool,
+
+    /// Extra directory to watch for changes in --watch mode, may be passed multiple times
+    #[arg(long = "watch-path")]
+    watch_paths: Option<Vec<String>>,
+
+    /// Kill a check's script if it runs longer than this (e.g. '30s', '2m')
+    #[arg(long, value_parser = parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Per-check timeout override in the format 'title:duration', may be passed multiple times
+    #[arg(long = "timeout-for", value_parser = parse_timeout_override, action = clap::ArgAction::Append)]
+    timeout_overrides: Option<Vec<(String, Duration)>>,
+
+    /// Re-run a failing check up to N times before declaring it failed
+    #[arg(long, default_value_t = 0)]
+    retries: u32,
+
+    /// Delay between retries (e.g. '5s'), only used with --retries
+    #[arg(long = "retry-delay", value_parser = parse_duration, default_value = "0s")]
+    retry_delay: Duration,
+
+    /// Print the execution plan (order, resolved paths, existence) without running anything
+    #[arg(long = "dry-run", default_value_t = false)]
+    dry_run: bool,
+
     /// The alternating titles and paths to the scripts ('title'='path')
     #[arg(value_parser = parse_title_path_pair)]
     pairs: Vec<(String, String)>,
@@ -61,6 +98,25 @@ fn parse_label_pair(s: &str) -> Result<(String, String), String> {
     Ok((parts[0].to_string(), parts[1].to_string()))
 }
 
+fn parse_depends_pair(s: &str) -> Result<(String, String), String> {
+    let parts: Vec<&str> = s.split(':').collect();
+    if parts.len() != 2 {
+        return Err("Dependency pair must be in the format 'child:parent'".to_string());
+    }
+    Ok((parts[0].to_string(), parts[1].to_string()))
+}
+
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    humantime::parse_duration(s).map_err(|err| err.to_string())
+}
+
+fn parse_timeout_override(s: &str) -> Result<(String, Duration), String> {
+    let (title, duration) = s
+        .split_once(':')
+        .ok_or_else(|| "Timeout override must be in the format 'title:duration'".to_string())?;
+    Ok((title.to_string(), parse_duration(duration)?))
+}
+
 impl Args {
     fn get_label_map(&self) -> IndexMap<String, String> {
         let mut map = IndexMap::new();
@@ -87,58 +143,372 @@ fn main() {
         (Arc::new(manager), handle)
     };
 
-    // Create ScriptContainers before spawning threads
+    let script_defs = args.pairs;
+    let depends = args.depends.unwrap_or_default();
+    let timeouts = Timeouts::new(args.timeout, args.timeout_overrides.unwrap_or_default());
+    let retry_policy = RetryPolicy {
+        retries: args.retries,
+        delay: args.retry_delay,
+    };
 
+    if args.dry_run {
+        let all_exist = render_plan(&script_defs, &depends, &output_manager);
+        output_manager.send(OutputCommand::Terminate);
+        output_manager_handle.join().unwrap();
+        if !all_exist {
+            exit(1);
+        }
+        return;
+    }
+
+    let all_successful = execute_run(
+        &script_defs,
+        &depends,
+        args.jobs,
+        &timeouts,
+        &retry_policy,
+        &output_manager,
+    );
+
+    if args.watch {
+        watch_and_rerun(
+            &script_defs,
+            &depends,
+            args.jobs,
+            &timeouts,
+            &retry_policy,
+            &output_manager,
+            &args.watch_paths.unwrap_or_default(),
+        );
+    }
+
+    output_manager.send(OutputCommand::Terminate);
+    output_manager_handle.join().unwrap();
+
+    // After all threads complete, exit with the appropriate status
+    if !all_successful {
+        exit(1);
+    }
+}
+
+/// Renders the scheduling order, resolved path and existence of every
+/// check without running anything, `factotum`'s `simulation_text`-style.
+/// Returns whether every script path resolved to an existing file.
+fn render_plan(
+    script_defs: &[(String, String)],
+    depends: &[(String, String)],
+    output_manager: &Arc<OutputManager>,
+) -> bool {
+    let scripts = script_defs
+        .iter()
+        .map(|(title, path)| Script {
+            title: title.clone(),
+            path: path.clone(),
+        })
+        .collect::<Vec<Script>>();
+
+    let mut queue = match DependencyQueue::build(scripts, depends.to_vec()) {
+        Ok(queue) => queue,
+        Err(message) => {
+            eprintln!("{}", message);
+            exit(1);
+        }
+    };
+
+    let mut all_exist = true;
+    for (position, script) in queue.simulate_order().into_iter().enumerate() {
+        let exists = Path::new(&script.path).exists();
+        all_exist &= exists;
+        output_manager.send(OutputCommand::Plan {
+            position: position + 1,
+            title: script.title,
+            path: script.path,
+            exists,
+        });
+    }
+
+    all_exist
+}
+
+/// Runs one full pass over `script_defs` respecting `depends`, blocking
+/// until every reachable check has completed. Returns whether every check
+/// that ran, ran successfully.
+fn execute_run(
+    script_defs: &[(String, String)],
+    depends: &[(String, String)],
+    jobs: usize,
+    timeouts: &Timeouts,
+    retry_policy: &RetryPolicy,
+    output_manager: &Arc<OutputManager>,
+) -> bool {
     let mut handles = vec![];
-    let mut scripts = args
-        .pairs
-        .into_iter()
-        .map(|(title, path)| Script { title, path })
+    let scripts = script_defs
+        .iter()
+        .map(|(title, path)| Script {
+            title: title.clone(),
+            path: path.clone(),
+        })
         .collect::<Vec<Script>>();
-    scripts.reverse();
 
-    let scripts = Arc::new(Mutex::new(scripts));
+    let queue = match DependencyQueue::build(scripts, depends.to_vec()) {
+        Ok(queue) => queue,
+        Err(message) => {
+            eprintln!("{}", message);
+            exit(1);
+        }
+    };
+    let queue = Arc::new(Mutex::new(queue));
 
-    // Near the start of main(), after creating output_manager:
     let all_successful = Arc::new(AtomicBool::new(true));
+    let jobserver_client = acquire_jobserver_client().map(Arc::new);
 
-    // Modify the thread spawning section to include all_successful:
-    for _ in 0..args.jobs {
-        let scripts_mutex = Arc::clone(&scripts);
-        let output_manager = Arc::clone(&output_manager);
+    for worker_index in 0..jobs {
+        let queue_mutex = Arc::clone(&queue);
+        let output_manager = Arc::clone(output_manager);
         let all_successful = Arc::clone(&all_successful);
+        let timeouts = timeouts.clone();
+        let retry_policy = retry_policy.clone();
+        let jobserver_client = jobserver_client.clone();
+        // The process already holds one implicit jobserver token (granted by
+        // the parent make/Nix build without reading the pipe); reserve it
+        // for a single worker so there is always at least one runnable slot
+        // even when the shared pool starts out empty.
+        let holds_implicit_token = worker_index == 0;
 
         let handle = thread::spawn(move || {
             loop {
                 let script = {
-                    let mut script_mutex_guard = scripts_mutex.lock().unwrap();
-                    if script_mutex_guard.is_empty() {
-                        break;
+                    let mut queue_guard = queue_mutex.lock().unwrap();
+                    match queue_guard.pop_ready() {
+                        Some(script) => script,
+                        None => {
+                            if queue_guard.is_finished() {
+                                break;
+                            }
+                            drop(queue_guard);
+                            thread::sleep(Duration::from_millis(10));
+                            continue;
+                        }
                     }
-                    script_mutex_guard.pop().unwrap()
                 };
 
-                run_script(script, output_manager.clone(), all_successful.clone());
+                // When a jobserver is available (e.g. invoked from `make -jN`
+                // or a Nix build), a worker waits for a shared token before
+                // running its script instead of relying solely on `jobs`.
+                let token = if holds_implicit_token {
+                    None
+                } else {
+                    acquire_token(&jobserver_client)
+                };
+
+                let title = script.title.clone();
+                let timeout = timeouts.for_title(&title);
+                let success = run_script(
+                    script,
+                    output_manager.clone(),
+                    all_successful.clone(),
+                    timeout,
+                    &retry_policy,
+                );
+                drop(token);
+
+                let mut queue_guard = queue_mutex.lock().unwrap();
+                if success {
+                    queue_guard.complete(&title);
+                } else {
+                    for (title, reason) in queue_guard.skip_descendants(&title) {
+                        output_manager.send(OutputCommand::SkipTask { title, reason });
+                    }
+                }
             }
         });
         handles.push(handle);
     }
 
-    // Wait for all threads to complete
     for handle in handles {
         handle.join().unwrap();
     }
 
-    output_manager.send(OutputCommand::Terminate);
-    output_manager_handle.join().unwrap();
+    all_successful.load(Ordering::SeqCst)
+}
 
-    // After all threads complete, exit with the appropriate status
-    if !all_successful.load(Ordering::SeqCst) {
-        exit(1);
+/// Watches every script path (plus any `--watch-path` directory) for
+/// changes and re-runs the affected checks, `deno ... --watch`-style,
+/// until the process is interrupted.
+fn watch_and_rerun(
+    script_defs: &[(String, String)],
+    depends: &[(String, String)],
+    jobs: usize,
+    timeouts: &Timeouts,
+    retry_policy: &RetryPolicy,
+    output_manager: &Arc<OutputManager>,
+    extra_watch_paths: &[String],
+) {
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher =
+        notify::recommended_watcher(tx).expect("Failed to start filesystem watcher");
+
+    for (_, path) in script_defs {
+        if let Err(err) = watcher.watch(Path::new(path), RecursiveMode::NonRecursive) {
+            eprintln!("Could not watch {}: {}", path, err);
+        }
+    }
+    for path in extra_watch_paths {
+        if let Err(err) = watcher.watch(Path::new(path), RecursiveMode::Recursive) {
+            eprintln!("Could not watch {}: {}", path, err);
+        }
+    }
+
+    loop {
+        // Block for the first change, then debounce any further ones for ~200ms
+        // so a batch of saves (e.g. from an editor) triggers a single re-run.
+        let Ok(first_event) = rx.recv() else {
+            break;
+        };
+        let mut changed = HashSet::new();
+        collect_event_paths(first_event, &mut changed);
+        while let Ok(event) = rx.recv_timeout(Duration::from_millis(200)) {
+            collect_event_paths(event, &mut changed);
+        }
+
+        let affected = affected_script_defs(script_defs, &changed);
+        let (to_run, to_run_depends): (&[(String, String)], Vec<(String, String)>) =
+            if affected.is_empty() {
+                (script_defs, depends.to_vec())
+            } else {
+                let titles: HashSet<&str> =
+                    affected.iter().map(|(title, _)| title.as_str()).collect();
+                let filtered_depends = depends
+                    .iter()
+                    .filter(|(child, parent)| {
+                        titles.contains(child.as_str()) && titles.contains(parent.as_str())
+                    })
+                    .cloned()
+                    .collect();
+                (&affected, filtered_depends)
+            };
+
+        output_manager.send(OutputCommand::Reset);
+        execute_run(
+            to_run,
+            &to_run_depends,
+            jobs,
+            timeouts,
+            retry_policy,
+            output_manager,
+        );
+    }
+}
+
+fn collect_event_paths(event: notify::Result<notify::Event>, changed: &mut HashSet<PathBuf>) {
+    if let Ok(event) = event {
+        changed.extend(event.paths);
+    }
+}
+
+/// Scripts whose own path is among `changed`; empty if the change can't be
+/// attributed to a specific script (e.g. it came from a `--watch-path` directory).
+/// `notify` reports canonicalized/absolute paths while `pairs` are typically
+/// passed relative, so both sides are canonicalized before comparing.
+fn affected_script_defs(
+    script_defs: &[(String, String)],
+    changed: &HashSet<PathBuf>,
+) -> Vec<(String, String)> {
+    let changed_canonical: HashSet<PathBuf> = changed
+        .iter()
+        .map(|path| std::fs::canonicalize(path).unwrap_or_else(|_| path.clone()))
+        .collect();
+
+    script_defs
+        .iter()
+        .filter(|(_, path)| {
+            let canonical =
+                std::fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+            changed_canonical.contains(&canonical)
+        })
+        .cloned()
+        .collect()
+}
+
+/// The GNU Make jobserver client, present only when the `jobserver` feature
+/// is compiled in and the crate is actually available.
+#[cfg(feature = "jobserver")]
+type JobserverClient = jobserver::Client;
+#[cfg(not(feature = "jobserver"))]
+type JobserverClient = ();
+
+/// Looks for a jobserver advertised via `MAKEFLAGS` (set by a parent
+/// `make -jN` / Nix build) so this run shares the global token pool instead
+/// of oversubscribing the host. Returns `None` (falling back to the fixed
+/// `--jobs` thread pool) when the feature is off or no jobserver is present.
+#[cfg(feature = "jobserver")]
+fn acquire_jobserver_client() -> Option<JobserverClient> {
+    // SAFETY: MAKEFLAGS, when present, was set by a parent make/Nix process
+    // that already owns the jobserver pipe fds; we only read from it here.
+    unsafe { jobserver::Client::from_env() }
+}
+#[cfg(not(feature = "jobserver"))]
+fn acquire_jobserver_client() -> Option<JobserverClient> {
+    None
+}
+
+/// Blocks for one jobserver token, if a client is configured, keeping the
+/// returned guard held for the duration of the script run and releasing it
+/// (by dropping the guard) once that run completes.
+#[cfg(feature = "jobserver")]
+fn acquire_token(client: &Option<Arc<JobserverClient>>) -> Option<jobserver::Acquired> {
+    client
+        .as_ref()
+        .map(|client| client.acquire().expect("failed to acquire jobserver token"))
+}
+#[cfg(not(feature = "jobserver"))]
+fn acquire_token(_client: &Option<Arc<JobserverClient>>) -> Option<()> {
+    None
+}
+
+/// Global `--timeout` plus per-check `--timeout-for` overrides.
+#[derive(Clone)]
+struct Timeouts {
+    default: Option<Duration>,
+    overrides: HashMap<String, Duration>,
+}
+
+impl Timeouts {
+    fn new(default: Option<Duration>, overrides: Vec<(String, Duration)>) -> Self {
+        Self {
+            default,
+            overrides: overrides.into_iter().collect(),
+        }
+    }
+
+    fn for_title(&self, title: &str) -> Option<Duration> {
+        self.overrides.get(title).copied().or(self.default)
     }
 }
 
-fn run_script(script: Script, output_manager: Arc<OutputManager>, all_successful: Arc<AtomicBool>) {
+/// `--retries` and `--retry-delay`: how many extra times to re-execute a
+/// failing script before declaring it failed, and how long to wait between.
+#[derive(Clone)]
+struct RetryPolicy {
+    retries: u32,
+    delay: Duration,
+}
+
+/// Outcome of a single execution attempt, before retries are considered.
+struct AttemptOutcome {
+    /// `None` when the attempt timed out (always terminal, never retried)
+    success: Option<bool>,
+    duration: Duration,
+    output: Option<String>,
+}
+
+fn run_script(
+    script: Script,
+    output_manager: Arc<OutputManager>,
+    all_successful: Arc<AtomicBool>,
+    timeout: Option<Duration>,
+    retry_policy: &RetryPolicy,
+) -> bool {
     let script_path = script.path.as_str();
 
     if !Path::new(script_path).exists() {
@@ -147,46 +517,276 @@ fn run_script(script: Script, output_manager: Arc<OutputManager>, all_successful
             message: format!("{} does not exist", script_path),
         });
         all_successful.store(false, Ordering::SeqCst);
-        return;
+        return false;
     }
 
     output_manager.send(OutputCommand::AddTask(script.title.clone()));
 
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let attempt = attempt_script(script_path, timeout);
+
+        let Some(success) = attempt.success else {
+            // Timed out: terminal, never retried.
+            output_manager.send(OutputCommand::Timeout {
+                title: script.title.clone(),
+                duration: attempt.duration,
+            });
+            all_successful.store(false, Ordering::SeqCst);
+            return false;
+        };
+
+        if success {
+            output_manager.send(OutputCommand::CompleteTask {
+                title: script.title.clone(),
+                success: true,
+                flaky: attempts > 1,
+                attempts,
+                duration: attempt.duration,
+                output: None,
+            });
+            return true;
+        }
+
+        if attempts > retry_policy.retries {
+            all_successful.store(false, Ordering::SeqCst);
+            output_manager.send(OutputCommand::CompleteTask {
+                title: script.title.clone(),
+                success: false,
+                flaky: false,
+                attempts,
+                duration: attempt.duration,
+                output: attempt.output,
+            });
+            return false;
+        }
+
+        thread::sleep(retry_policy.delay);
+    }
+}
+
+/// Runs `script_path` once (honoring `timeout`) and checks it against any
+/// embedded `#= ` assertion spec.
+fn attempt_script(script_path: &str, timeout: Option<Duration>) -> AttemptOutcome {
     let start = Instant::now();
-    let result = Command::new(script_path)
-        .output()
-        .expect("Failed to execute script");
+    let outcome = execute_with_timeout(script_path, timeout).expect("Failed to execute script");
     let duration = start.elapsed();
 
+    if outcome.timed_out {
+        return AttemptOutcome {
+            success: None,
+            duration,
+            output: None,
+        };
+    }
+    let status = outcome.status.expect("process exited without a status");
+
+    let stdout = String::from_utf8_lossy(&outcome.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&outcome.stderr).to_string();
+    let assertion_mismatches = match parse_expectation(script_path) {
+        Ok(Some(expectation)) => match expectation.check(&stdout, &stderr, status.code()) {
+            Ok(mismatches) => mismatches,
+            Err(message) => vec![message],
+        },
+        Ok(None) => Vec::new(),
+        Err(message) => vec![message],
+    };
+
+    let success = status.success() && assertion_mismatches.is_empty();
+
     let mut output = None;
-    if !result.status.success() {
-        all_successful.store(false, Ordering::SeqCst);
+    if !success {
         let mut output_lines = Vec::new();
-        if !result.stdout.is_empty() {
+        if !outcome.stdout.is_empty() {
             output_lines.push("Output:".to_string());
-            output_lines.extend(
-                String::from_utf8_lossy(&result.stdout)
-                    .lines()
-                    .map(|s| s.to_string()),
-            );
+            output_lines.extend(stdout.lines().map(|s| s.to_string()));
         }
-        if !result.stderr.is_empty() {
+        if !outcome.stderr.is_empty() {
             output_lines.push("Error:".to_string());
-            output_lines.extend(
-                String::from_utf8_lossy(&result.stderr)
-                    .lines()
-                    .map(|s| s.to_string()),
-            );
+            output_lines.extend(stderr.lines().map(|s| s.to_string()));
         }
+        output_lines.extend(assertion_mismatches);
         output = Some(output_lines.join("\n"));
     }
 
-    output_manager.send(OutputCommand::CompleteTask {
-        title: script.title.clone(),
-        success: result.status.success(),
+    AttemptOutcome {
+        success: Some(success),
         duration,
         output,
+    }
+}
+
+/// Result of running a script to completion or to its timeout deadline.
+struct RunOutcome {
+    /// `None` only when the process was killed for overrunning its deadline
+    status: Option<std::process::ExitStatus>,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    timed_out: bool,
+}
+
+/// Spawns `script_path`, polling for completion instead of blocking
+/// unbounded on `Command::output()`. If `timeout` elapses first, the child
+/// is sent `SIGTERM`, given a short grace period, then `SIGKILL`ed.
+fn execute_with_timeout(
+    script_path: &str,
+    timeout: Option<Duration>,
+) -> std::io::Result<RunOutcome> {
+    use std::io::Read;
+    use std::os::unix::process::CommandExt;
+    use std::process::Stdio;
+    use std::sync::mpsc;
+
+    let mut child = Command::new(script_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Put the child in its own process group (pgid == its own pid) so a
+        // timeout can signal any grandchild it backgrounds too, not just the
+        // direct child — otherwise a backgrounded grandchild can keep the
+        // stdout/stderr pipes open forever after the direct child is killed.
+        .process_group(0)
+        .spawn()?;
+
+    let mut stdout_pipe = child.stdout.take().expect("stdout was piped");
+    let mut stderr_pipe = child.stderr.take().expect("stderr was piped");
+    let (stdout_tx, stdout_rx) = mpsc::channel();
+    let (stderr_tx, stderr_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout_pipe.read_to_end(&mut buf);
+        let _ = stdout_tx.send(buf);
     });
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stderr_pipe.read_to_end(&mut buf);
+        let _ = stderr_tx.send(buf);
+    });
+
+    let deadline = timeout.map(|t| Instant::now() + t);
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            timed_out = true;
+            kill_with_grace(&mut child);
+            break child.wait().ok();
+        }
+        thread::sleep(Duration::from_millis(25));
+    };
+
+    // The process group kill above should have taken any backgrounded
+    // grandchild down with it, closing its end of the pipes. As a backstop
+    // against one that escaped the group anyway, don't let the reader
+    // threads block this worker forever — fall back to whatever was
+    // captured so far once the grace period passes.
+    let reader_grace = Duration::from_secs(2);
+    let stdout = stdout_rx.recv_timeout(reader_grace).unwrap_or_default();
+    let stderr = stderr_rx.recv_timeout(reader_grace).unwrap_or_default();
+
+    Ok(RunOutcome {
+        status,
+        stdout,
+        stderr,
+        timed_out,
+    })
+}
+
+/// Sends `SIGTERM` to the whole process group, waits up to two seconds for
+/// a graceful exit, then escalates to `SIGKILL`.
+fn kill_with_grace(child: &mut std::process::Child) {
+    let pgid = nix::unistd::Pid::from_raw(child.id() as i32);
+    let _ = nix::sys::signal::killpg(pgid, nix::sys::signal::Signal::SIGTERM);
+
+    let grace_deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < grace_deadline {
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            return;
+        }
+        thread::sleep(Duration::from_millis(25));
+    }
+
+    let _ = nix::sys::signal::killpg(pgid, nix::sys::signal::Signal::SIGKILL);
+    let _ = child.kill();
+}
+
+/// Expectations embedded in a script as `#= {...}` leading comment lines,
+/// e.g. `#= {"stdout": "^HTTP/1\\.1 200", "stderr": "", "exit": 0}`, checked
+/// against the captured output in addition to the usual exit-code check.
+#[derive(serde::Deserialize)]
+struct Expectation {
+    stdout: Option<String>,
+    stderr: Option<String>,
+    exit: Option<i32>,
+}
+
+impl Expectation {
+    /// Returns a human-readable mismatch ("expected ... vs. actual ...") for
+    /// every field that doesn't match.
+    fn check(&self, stdout: &str, stderr: &str, exit_code: Option<i32>) -> Result<Vec<String>, String> {
+        let mut mismatches = Vec::new();
+
+        if let Some(pattern) = &self.stdout {
+            let regex = regex::Regex::new(pattern)
+                .map_err(|err| format!("invalid stdout expectation '{}': {}", pattern, err))?;
+            if !regex.is_match(stdout) {
+                mismatches.push(format!(
+                    "Assertion failed: stdout did not match /{}/\nActual stdout: {}",
+                    pattern, stdout
+                ));
+            }
+        }
+        if let Some(pattern) = &self.stderr {
+            let regex = regex::Regex::new(pattern)
+                .map_err(|err| format!("invalid stderr expectation '{}': {}", pattern, err))?;
+            if !regex.is_match(stderr) {
+                mismatches.push(format!(
+                    "Assertion failed: stderr did not match /{}/\nActual stderr: {}",
+                    pattern, stderr
+                ));
+            }
+        }
+        if let Some(expected_exit) = self.exit {
+            if exit_code != Some(expected_exit) {
+                mismatches.push(format!(
+                    "Assertion failed: expected exit code {}, got {:?}",
+                    expected_exit, exit_code
+                ));
+            }
+        }
+
+        Ok(mismatches)
+    }
+}
+
+/// Reads the contiguous `#= ` leading comment block off the front of a
+/// script and parses its concatenated content as a single JSON
+/// [`Expectation`] object. Returns `Ok(None)` when the script has no such
+/// block, or isn't readable as text at all (e.g. a compiled binary
+/// healthcheck) — absence of a spec is not an assertion failure.
+fn parse_expectation(script_path: &str) -> Result<Option<Expectation>, String> {
+    let Ok(contents) = std::fs::read_to_string(script_path) else {
+        return Ok(None);
+    };
+
+    let spec: String = contents
+        .lines()
+        // A shebang (and any blank padding around it) precedes the spec in
+        // virtually every real script; skip past it before looking for the
+        // `#= ` block instead of letting it look like the block's end.
+        .skip_while(|line| line.starts_with("#!") || line.trim().is_empty())
+        .map_while(|line| line.strip_prefix("#= "))
+        .collect();
+
+    if spec.is_empty() {
+        return Ok(None);
+    }
+
+    serde_json::from_str(&spec)
+        .map(Some)
+        .map_err(|err| format!("invalid assertion spec in {}: {}", script_path, err))
 }
 
 /// containing all the information needed to print user-friendly output.
@@ -198,6 +798,186 @@ struct Script {
     path: String,
 }
 
+/// Work pool that hands scripts out in dependency order instead of flat
+/// arbitrary order, modeled on Cargo's build-plan scheduler: a ready queue
+/// of scripts whose prerequisites have all completed, plus the bookkeeping
+/// needed to move a script from "blocked" to "ready" as its parents finish.
+struct DependencyQueue {
+    /// scripts whose remaining dependency count has reached zero
+    ready: Vec<Script>,
+
+    /// scripts that are still waiting on at least one dependency, keyed by title
+    pending: HashMap<String, Script>,
+
+    /// unfinished prerequisite titles of each not-yet-ready script
+    deps: HashMap<String, HashSet<String>>,
+
+    /// titles that depend on a given title, i.e. the inverse of `deps`
+    reverse_deps: HashMap<String, Vec<String>>,
+}
+
+impl DependencyQueue {
+    /// Builds the queue from the resolved scripts and `child:parent` pairs
+    /// collected from `--depends`, detecting duplicate titles, unknown
+    /// titles, and cycles up front.
+    fn build(scripts: Vec<Script>, depends: Vec<(String, String)>) -> Result<Self, String> {
+        let titles: HashSet<String> = scripts.iter().map(|s| s.title.clone()).collect();
+        if titles.len() != scripts.len() {
+            let mut seen = HashSet::new();
+            let mut duplicates: Vec<&str> = scripts
+                .iter()
+                .map(|s| s.title.as_str())
+                .filter(|title| !seen.insert(*title))
+                .collect();
+            duplicates.sort();
+            duplicates.dedup();
+            return Err(format!(
+                "duplicate check title(s), titles must be unique to be scheduled: {}",
+                duplicates.join(", ")
+            ));
+        }
+
+        let mut deps: HashMap<String, HashSet<String>> =
+            titles.iter().map(|t| (t.clone(), HashSet::new())).collect();
+        let mut reverse_deps: HashMap<String, Vec<String>> =
+            titles.iter().map(|t| (t.clone(), Vec::new())).collect();
+
+        for (child, parent) in &depends {
+            if !titles.contains(child) {
+                return Err(format!("--depends references unknown check '{}'", child));
+            }
+            if !titles.contains(parent) {
+                return Err(format!("--depends references unknown check '{}'", parent));
+            }
+            deps.get_mut(child).unwrap().insert(parent.clone());
+            reverse_deps.get_mut(parent).unwrap().push(child.clone());
+        }
+
+        Self::check_for_cycles(&deps)?;
+
+        let mut ready = Vec::new();
+        let mut pending = HashMap::new();
+        for script in scripts {
+            if deps[&script.title].is_empty() {
+                ready.push(script);
+            } else {
+                pending.insert(script.title.clone(), script);
+            }
+        }
+
+        Ok(Self {
+            ready,
+            pending,
+            deps,
+            reverse_deps,
+        })
+    }
+
+    /// Aborts with a clear error if some check can never reach zero remaining
+    /// deps, i.e. it sits on a dependency cycle.
+    fn check_for_cycles(deps: &HashMap<String, HashSet<String>>) -> Result<(), String> {
+        let mut remaining: HashMap<String, HashSet<String>> = deps.clone();
+        let mut resolved: HashSet<String> = HashSet::new();
+
+        loop {
+            let newly_resolved: Vec<String> = remaining
+                .iter()
+                .filter(|(_, unfinished)| unfinished.is_empty())
+                .map(|(title, _)| title.clone())
+                .collect();
+
+            if newly_resolved.is_empty() {
+                break;
+            }
+
+            for title in &newly_resolved {
+                remaining.remove(title);
+                resolved.insert(title.clone());
+            }
+            for unfinished in remaining.values_mut() {
+                for title in &newly_resolved {
+                    unfinished.remove(title);
+                }
+            }
+        }
+
+        if remaining.is_empty() {
+            Ok(())
+        } else {
+            let mut cycle: Vec<&str> = remaining.keys().map(|s| s.as_str()).collect();
+            cycle.sort();
+            Err(format!(
+                "--depends forms a cycle that can never be scheduled: {}",
+                cycle.join(", ")
+            ))
+        }
+    }
+
+    fn pop_ready(&mut self) -> Option<Script> {
+        self.ready.pop()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.ready.is_empty() && self.pending.is_empty()
+    }
+
+    /// Drains the queue in scheduling order without executing anything,
+    /// assuming every node completes successfully. Used by `--dry-run` to
+    /// show the plan a real run would follow.
+    fn simulate_order(&mut self) -> Vec<Script> {
+        let mut order = Vec::new();
+        while let Some(script) = self.pop_ready() {
+            self.complete(&script.title);
+            order.push(script);
+        }
+        order
+    }
+
+    /// Marks `title` as finished successfully, promoting any dependent whose
+    /// last remaining prerequisite was `title` into the ready queue.
+    fn complete(&mut self, title: &str) {
+        let Some(children) = self.reverse_deps.get(title).cloned() else {
+            return;
+        };
+        for child in children {
+            let Some(unfinished) = self.deps.get_mut(&child) else {
+                continue;
+            };
+            unfinished.remove(title);
+            if unfinished.is_empty() {
+                if let Some(script) = self.pending.remove(&child) {
+                    self.ready.push(script);
+                }
+            }
+        }
+    }
+
+    /// `title` failed (or was skipped itself), so every not-yet-run
+    /// descendant can never run; remove them from the queue and return
+    /// `(title, reason)` pairs for reporting, each listed after the
+    /// check it was reached through.
+    fn skip_descendants(&mut self, title: &str) -> Vec<(String, String)> {
+        let reason = format!("dependency '{}' did not pass", title);
+        let mut skipped = Vec::new();
+        let mut frontier = vec![title.to_string()];
+
+        while let Some(parent) = frontier.pop() {
+            let Some(children) = self.reverse_deps.get(&parent).cloned() else {
+                continue;
+            };
+            for child in children {
+                self.deps.remove(&child);
+                if self.pending.remove(&child).is_some() {
+                    skipped.push((child.clone(), reason.clone()));
+                    frontier.push(child);
+                }
+            }
+        }
+
+        skipped
+    }
+}
+
 impl Script {
     #[allow(dead_code)]
     fn new(path: String) -> Self {
@@ -0,0 +1,78 @@
+use crate::printer;
+use clap::ValueEnum;
+use indexmap::IndexMap;
+use std::sync::mpsc::{self, Sender};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// The style of output to render, selected via `--style`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrinterTypes {
+    Emoji,
+    Prometheus,
+}
+
+/// Messages sent from the worker threads to the printer thread.
+pub enum OutputCommand {
+    AddTask(String),
+    CompleteTask {
+        title: String,
+        success: bool,
+        /// true when the check failed at least once before eventually passing
+        flaky: bool,
+        /// total number of times the check was executed
+        attempts: u32,
+        duration: Duration,
+        output: Option<String>,
+    },
+    Error {
+        title: String,
+        message: String,
+    },
+    Timeout {
+        title: String,
+        duration: Duration,
+    },
+    SkipTask {
+        title: String,
+        reason: String,
+    },
+    Plan {
+        position: usize,
+        title: String,
+        path: String,
+        exists: bool,
+    },
+    /// Clears accumulated state between `--watch` runs
+    Reset,
+    Terminate,
+}
+
+/// Hands `OutputCommand`s off to a dedicated printer thread so worker
+/// threads never block on stdout.
+pub struct OutputManager {
+    sender: Sender<OutputCommand>,
+}
+
+impl OutputManager {
+    pub fn new(style: PrinterTypes, labels: IndexMap<String, String>) -> (Self, JoinHandle<()>) {
+        let (sender, receiver) = mpsc::channel();
+
+        let handle = std::thread::spawn(move || {
+            let mut printer = printer::Printer::new(style, labels);
+            for command in receiver {
+                if matches!(command, OutputCommand::Terminate) {
+                    break;
+                }
+                printer.handle(command);
+            }
+            printer.finish();
+        });
+
+        (Self { sender }, handle)
+    }
+
+    pub fn send(&self, command: OutputCommand) {
+        let _ = self.sender.send(command);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::output_manager::{OutputCommand, PrinterTypes};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Per-check result the printer keeps around so it can render a final
+/// Prometheus summary once every task has reported in.
+struct TaskResult {
+    success: bool,
+    flaky: bool,
+    attempts: u32,
+    duration: Duration,
+}
+
+/// Renders `OutputCommand`s in either the emoji or prometheus style.
+pub struct Printer {
+    style: PrinterTypes,
+    labels: IndexMap<String, String>,
+    results: HashMap<String, TaskResult>,
+    order: Vec<String>,
+}
+
+impl Printer {
+    pub fn new(style: PrinterTypes, labels: IndexMap<String, String>) -> Self {
+        Self {
+            style,
+            labels,
+            results: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    pub fn handle(&mut self, command: OutputCommand) {
+        match command {
+            OutputCommand::AddTask(title) => self.add_task(title),
+            OutputCommand::CompleteTask {
+                title,
+                success,
+                flaky,
+                attempts,
+                duration,
+                output,
+            } => self.complete_task(title, success, flaky, attempts, duration, output),
+            OutputCommand::Error { title, message } => self.error(title, message),
+            OutputCommand::Timeout { title, duration } => self.timeout(title, duration),
+            OutputCommand::SkipTask { title, reason } => self.skip_task(title, reason),
+            OutputCommand::Plan {
+                position,
+                title,
+                path,
+                exists,
+            } => self.plan(position, title, path, exists),
+            OutputCommand::Reset => self.reset(),
+            OutputCommand::Terminate => {}
+        }
+    }
+
+    fn add_task(&mut self, title: String) {
+        if matches!(self.style, PrinterTypes::Emoji) {
+            println!("🏃 {}", title);
+        }
+        self.order.push(title);
+    }
+
+    fn complete_task(
+        &mut self,
+        title: String,
+        success: bool,
+        flaky: bool,
+        attempts: u32,
+        duration: Duration,
+        output: Option<String>,
+    ) {
+        if matches!(self.style, PrinterTypes::Emoji) {
+            let icon = if !success {
+                "❌"
+            } else if flaky {
+                "🤪"
+            } else {
+                "✅"
+            };
+            println!("{} {} ({:.2?}, {} attempt(s))", icon, title, duration, attempts);
+            if let Some(output) = &output {
+                println!("{}", output);
+            }
+        }
+
+        self.results.insert(
+            title,
+            TaskResult {
+                success,
+                flaky,
+                attempts,
+                duration,
+            },
+        );
+    }
+
+    fn error(&mut self, title: String, message: String) {
+        if matches!(self.style, PrinterTypes::Emoji) {
+            println!("⚠️  {}: {}", title, message);
+        }
+        self.results.insert(
+            title,
+            TaskResult {
+                success: false,
+                flaky: false,
+                attempts: 0,
+                duration: Duration::default(),
+            },
+        );
+    }
+
+    fn timeout(&mut self, title: String, duration: Duration) {
+        if matches!(self.style, PrinterTypes::Emoji) {
+            println!("⏱️  {} timed out after {:.2?}", title, duration);
+        }
+        self.results.insert(
+            title,
+            TaskResult {
+                success: false,
+                flaky: false,
+                attempts: 0,
+                duration,
+            },
+        );
+    }
+
+    fn skip_task(&mut self, title: String, reason: String) {
+        if matches!(self.style, PrinterTypes::Emoji) {
+            println!("⏭️  {} skipped: {}", title, reason);
+        }
+        self.results.insert(
+            title,
+            TaskResult {
+                success: false,
+                flaky: false,
+                attempts: 0,
+                duration: Duration::default(),
+            },
+        );
+    }
+
+    fn plan(&mut self, position: usize, title: String, path: String, exists: bool) {
+        let marker = if exists { "✅" } else { "❌" };
+        println!("{:>3}. {} {} -> {}", position, marker, title, path);
+    }
+
+    fn reset(&mut self) {
+        self.results.clear();
+        self.order.clear();
+    }
+
+    /// Emits the style-specific summary once the run has finished.
+    pub fn finish(&mut self) {
+        if matches!(self.style, PrinterTypes::Prometheus) {
+            self.print_prometheus_summary();
+        }
+    }
+
+    fn print_prometheus_summary(&self) {
+        let label_suffix: String = self
+            .labels
+            .iter()
+            .map(|(key, value)| format!(",{}=\"{}\"", key, value))
+            .collect();
+
+        for title in &self.order {
+            let Some(result) = self.results.get(title) else {
+                continue;
+            };
+
+            println!(
+                "healthcheck_success{{title=\"{}\"{}}} {}",
+                title,
+                label_suffix,
+                result.success as u8
+            );
+            println!(
+                "healthcheck_attempts{{title=\"{}\"{}}} {}",
+                title, label_suffix, result.attempts
+            );
+            println!(
+                "healthcheck_flaky{{title=\"{}\"{}}} {}",
+                title,
+                label_suffix,
+                result.flaky as u8
+            );
+            println!(
+                "healthcheck_duration_seconds{{title=\"{}\"{}}} {}",
+                title,
+                label_suffix,
+                result.duration.as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,266 @@
+#![cfg(test)]
+
+use crate::output_manager::{OutputCommand, OutputManager, PrinterTypes};
+use crate::*;
+use indexmap::IndexMap;
+use std::fs;
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A scratch path under the system temp dir, unique per test invocation.
+fn unique_path(name: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    std::env::temp_dir().join(format!(
+        "nixos-healthchecks-test-{}-{}-{}",
+        name,
+        std::process::id(),
+        nanos
+    ))
+}
+
+/// Writes `contents` to `path` and marks it executable.
+fn write_script(path: &PathBuf, contents: &str) {
+    let mut file = fs::File::create(path).unwrap();
+    file.write_all(contents.as_bytes()).unwrap();
+    let mut perms = fs::metadata(path).unwrap().permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(path, perms).unwrap();
+}
+
+// --- DependencyQueue -------------------------------------------------
+
+#[test]
+fn dependency_queue_orders_by_dependency() {
+    let scripts = vec![
+        Script {
+            title: "child".to_string(),
+            path: "/bin/true".to_string(),
+        },
+        Script {
+            title: "parent".to_string(),
+            path: "/bin/true".to_string(),
+        },
+    ];
+    let depends = vec![("child".to_string(), "parent".to_string())];
+    let mut queue = DependencyQueue::build(scripts, depends).unwrap();
+
+    assert_eq!(queue.pop_ready().map(|s| s.title), Some("parent".to_string()));
+    assert!(!queue.is_finished());
+    queue.complete("parent");
+    assert_eq!(queue.pop_ready().map(|s| s.title), Some("child".to_string()));
+}
+
+#[test]
+fn dependency_queue_detects_cycles() {
+    let scripts = vec![
+        Script {
+            title: "a".to_string(),
+            path: "/bin/true".to_string(),
+        },
+        Script {
+            title: "b".to_string(),
+            path: "/bin/true".to_string(),
+        },
+    ];
+    let depends = vec![
+        ("a".to_string(), "b".to_string()),
+        ("b".to_string(), "a".to_string()),
+    ];
+
+    assert!(DependencyQueue::build(scripts, depends).is_err());
+}
+
+#[test]
+fn dependency_queue_rejects_duplicate_titles() {
+    let scripts = vec![
+        Script {
+            title: "dup".to_string(),
+            path: "/bin/true".to_string(),
+        },
+        Script {
+            title: "dup".to_string(),
+            path: "/bin/false".to_string(),
+        },
+    ];
+
+    assert!(DependencyQueue::build(scripts, Vec::new()).is_err());
+}
+
+#[test]
+fn dependency_queue_skips_descendants_on_failure() {
+    let scripts = vec![
+        Script {
+            title: "root".to_string(),
+            path: "/bin/false".to_string(),
+        },
+        Script {
+            title: "child".to_string(),
+            path: "/bin/true".to_string(),
+        },
+        Script {
+            title: "grandchild".to_string(),
+            path: "/bin/true".to_string(),
+        },
+    ];
+    let depends = vec![
+        ("child".to_string(), "root".to_string()),
+        ("grandchild".to_string(), "child".to_string()),
+    ];
+    let mut queue = DependencyQueue::build(scripts, depends).unwrap();
+    assert_eq!(queue.pop_ready().map(|s| s.title), Some("root".to_string()));
+
+    let skipped: Vec<String> = queue
+        .skip_descendants("root")
+        .into_iter()
+        .map(|(title, _)| title)
+        .collect();
+
+    assert_eq!(skipped, vec!["child".to_string(), "grandchild".to_string()]);
+    assert!(queue.is_finished());
+}
+
+// --- parse_expectation / Expectation::check ---------------------------
+
+#[test]
+fn parse_expectation_skips_leading_shebang() {
+    let path = unique_path("shebang-spec");
+    write_script(
+        &path,
+        "#!/bin/sh\n#= {\"stdout\": \"^hello$\"}\necho nope\n",
+    );
+
+    let expectation = parse_expectation(path.to_str().unwrap()).unwrap();
+    fs::remove_file(&path).ok();
+
+    let expectation = expectation.expect("spec after a shebang should still be parsed");
+    let mismatches = expectation.check("nope\n", "", Some(0)).unwrap();
+    assert_eq!(
+        mismatches.len(),
+        1,
+        "deliberately mismatching stdout should be flagged, not silently passed"
+    );
+}
+
+#[test]
+fn parse_expectation_returns_none_without_spec() {
+    let path = unique_path("no-spec");
+    write_script(&path, "#!/bin/sh\necho hello\n");
+
+    let expectation = parse_expectation(path.to_str().unwrap()).unwrap();
+    fs::remove_file(&path).ok();
+
+    assert!(expectation.is_none());
+}
+
+#[test]
+fn parse_expectation_returns_none_for_unreadable_path() {
+    let expectation = parse_expectation("/does/not/exist/nixos-healthchecks-missing").unwrap();
+    assert!(expectation.is_none());
+}
+
+#[test]
+fn expectation_check_flags_stdout_mismatch() {
+    let expectation = Expectation {
+        stdout: Some("^hello$".to_string()),
+        stderr: None,
+        exit: None,
+    };
+
+    let mismatches = expectation.check("goodbye\n", "", Some(0)).unwrap();
+    assert_eq!(mismatches.len(), 1);
+}
+
+#[test]
+fn expectation_check_passes_when_everything_matches() {
+    let expectation = Expectation {
+        stdout: Some("^hello$".to_string()),
+        stderr: Some("^$".to_string()),
+        exit: Some(0),
+    };
+
+    let mismatches = expectation.check("hello", "", Some(0)).unwrap();
+    assert!(mismatches.is_empty());
+}
+
+// --- retry / flaky bookkeeping in run_script ---------------------------
+
+#[test]
+fn run_script_succeeds_after_retry_and_reports_success() {
+    let script_path = unique_path("flaky-script");
+    let marker_path = unique_path("flaky-marker");
+    write_script(
+        &script_path,
+        &format!(
+            "#!/bin/sh\nif [ -e {marker} ]; then exit 0; else touch {marker}; exit 1; fi\n",
+            marker = marker_path.display()
+        ),
+    );
+
+    let (manager, handle) = OutputManager::new(PrinterTypes::Emoji, IndexMap::new());
+    let output_manager = Arc::new(manager);
+    let all_successful = Arc::new(AtomicBool::new(true));
+    let retry_policy = RetryPolicy {
+        retries: 1,
+        delay: Duration::from_millis(10),
+    };
+    let script = Script {
+        title: "flaky".to_string(),
+        path: script_path.to_str().unwrap().to_string(),
+    };
+
+    let success = run_script(
+        script,
+        output_manager.clone(),
+        all_successful.clone(),
+        None,
+        &retry_policy,
+    );
+
+    output_manager.send(OutputCommand::Terminate);
+    handle.join().unwrap();
+    fs::remove_file(&script_path).ok();
+    fs::remove_file(&marker_path).ok();
+
+    assert!(success, "run_script should succeed once a retry is available");
+    assert!(all_successful.load(Ordering::SeqCst));
+}
+
+#[test]
+fn run_script_fails_without_enough_retries() {
+    let script_path = unique_path("always-fails");
+    write_script(&script_path, "#!/bin/sh\nexit 1\n");
+
+    let (manager, handle) = OutputManager::new(PrinterTypes::Emoji, IndexMap::new());
+    let output_manager = Arc::new(manager);
+    let all_successful = Arc::new(AtomicBool::new(true));
+    let retry_policy = RetryPolicy {
+        retries: 0,
+        delay: Duration::from_millis(0),
+    };
+    let script = Script {
+        title: "always-fails".to_string(),
+        path: script_path.to_str().unwrap().to_string(),
+    };
+
+    let success = run_script(
+        script,
+        output_manager.clone(),
+        all_successful.clone(),
+        None,
+        &retry_policy,
+    );
+
+    output_manager.send(OutputCommand::Terminate);
+    handle.join().unwrap();
+    fs::remove_file(&script_path).ok();
+
+    assert!(!success, "run_script should not retry past retry_policy.retries");
+    assert!(!all_successful.load(Ordering::SeqCst));
+}